@@ -2,23 +2,32 @@ use chrono::Utc;
 use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
 use serde::{Deserialize, Serialize};
 
+use crate::error::NotifyError;
+
+/// The number of trailing characters of an API key that encode the service id and secret key.
+const KEY_SUFFIX_LEN: usize = 73;
+
 #[derive(Debug, Deserialize, Serialize)]
 struct Claims {
     iss: String,
     iat: usize,
 }
 
-pub fn create_jwt(api_key: &String) -> Result<String, jsonwebtoken::errors::Error> {
+pub fn create_jwt(api_key: &str) -> Result<String, NotifyError> {
+    if api_key.len() < KEY_SUFFIX_LEN {
+        return Err(NotifyError::MalformedApiKey);
+    }
+
     let claims = Claims {
-        iss: String::from(service_id(&api_key)),
+        iss: String::from(service_id(api_key)),
         iat: Utc::now().timestamp() as usize,
     };
     let header = Header::new(Algorithm::HS256);
-    encode(
+    Ok(encode(
         &header,
         &claims,
-        &EncodingKey::from_secret(secret_key(&api_key)),
-    )
+        &EncodingKey::from_secret(secret_key(api_key)),
+    )?)
 }
 
 fn service_id(api_key: &str) -> String {
@@ -0,0 +1,177 @@
+//! Handling for GOV.UK Notify delivery receipt callbacks.
+//!
+//! Notify can be configured to POST a delivery receipt to a callback URL you host whenever a
+//! notification's status changes. This module only deals with parsing and authenticating that
+//! inbound payload; wiring it into an actual web server is left to the integrator.
+
+use serde::{Deserialize, Serialize};
+
+use crate::NotificationType;
+
+/// The status of a notification, as reported by a delivery receipt or by `GET /v2/notifications`.
+///
+/// Covers the in-flight states a notification passes through before Notify can report a
+/// terminal outcome, the email/SMS terminal states, and the letter-specific states Notify
+/// reports for postal notifications.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DeliveryStatus {
+    /// Notify has accepted the notification but has not yet tried to send it.
+    Created,
+    /// Notify is sending the notification to the provider.
+    Sending,
+    /// The SMS provider has queued the text message to be delivered.
+    Pending,
+    /// Notify has handed the notification to the provider; no delivery outcome yet.
+    Sent,
+    Delivered,
+    PermanentFailure,
+    TemporaryFailure,
+    TechnicalFailure,
+    /// The letter has been received by the printing provider.
+    Received,
+    /// The letter was cancelled before it was sent for printing.
+    Cancelled,
+    /// The letter has been accepted by the printing provider.
+    Accepted,
+    /// A status Notify added after this crate was last updated.
+    #[serde(other)]
+    Unknown,
+}
+
+impl DeliveryStatus {
+    /// The string Notify uses for this status in JSON bodies and query parameters.
+    /// `Unknown` has no Notify string of its own and gives `"unknown"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeliveryStatus::Created => "created",
+            DeliveryStatus::Sending => "sending",
+            DeliveryStatus::Pending => "pending",
+            DeliveryStatus::Sent => "sent",
+            DeliveryStatus::Delivered => "delivered",
+            DeliveryStatus::PermanentFailure => "permanent-failure",
+            DeliveryStatus::TemporaryFailure => "temporary-failure",
+            DeliveryStatus::TechnicalFailure => "technical-failure",
+            DeliveryStatus::Received => "received",
+            DeliveryStatus::Cancelled => "cancelled",
+            DeliveryStatus::Accepted => "accepted",
+            DeliveryStatus::Unknown => "unknown",
+        }
+    }
+}
+
+/// A delivery receipt POSTed by Notify to your configured callback URL.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DeliveryReceipt {
+    pub id: String,
+    pub reference: Option<String>,
+    pub to: String,
+    pub status: DeliveryStatus,
+    pub created_at: String,
+    pub completed_at: Option<String>,
+    pub sent_at: Option<String>,
+    pub notification_type: NotificationType,
+    pub template_id: String,
+    pub template_version: u32,
+}
+
+/// Parses a Notify delivery receipt callback body.
+pub fn parse_callback(body: &[u8]) -> Result<DeliveryReceipt, serde_json::Error> {
+    serde_json::from_slice(body)
+}
+
+/// Verifies the `Authorization` header Notify sends with each callback against the bearer
+/// token configured for the callback URL, in constant time.
+///
+/// `header` is the raw header value, e.g. `"Bearer abc123"`.
+pub fn verify_bearer_token(header: &str, expected: &str) -> bool {
+    let token = match header.strip_prefix("Bearer ") {
+        Some(t) => t,
+        None => return false,
+    };
+
+    constant_time_eq(token.as_bytes(), expected.as_bytes())
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    a.iter().zip(b.iter()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_email_delivery_receipt() {
+        let body = br#"{
+            "id": "740e5834-3a29-46b4-9a6f-16142fde533a",
+            "reference": "ref_unique_xyz",
+            "to": "john.doe@example.com",
+            "status": "delivered",
+            "created_at": "2021-02-24T14:00:00.000000Z",
+            "completed_at": "2021-02-24T14:01:00.000000Z",
+            "sent_at": "2021-02-24T14:00:30.000000Z",
+            "notification_type": "email",
+            "template_id": "217a419e-6a7d-482a-9596-718b889dffce",
+            "template_version": 1
+        }"#;
+
+        let receipt = parse_callback(body).unwrap();
+        assert_eq!(receipt.status, DeliveryStatus::Delivered);
+        assert_eq!(receipt.notification_type, NotificationType::Email);
+    }
+
+    #[test]
+    fn parses_letter_statuses() {
+        let body = br#"{
+            "id": "740e5834-3a29-46b4-9a6f-16142fde533a",
+            "reference": null,
+            "to": "Mr J Doe",
+            "status": "received",
+            "created_at": "2021-02-24T14:00:00.000000Z",
+            "completed_at": null,
+            "sent_at": null,
+            "notification_type": "letter",
+            "template_id": "217a419e-6a7d-482a-9596-718b889dffce",
+            "template_version": 1
+        }"#;
+
+        let receipt = parse_callback(body).unwrap();
+        assert_eq!(receipt.status, DeliveryStatus::Received);
+        assert_eq!(receipt.notification_type, NotificationType::Letter);
+    }
+
+    #[test]
+    fn parses_unknown_status_as_catch_all() {
+        let body = br#"{
+            "id": "740e5834-3a29-46b4-9a6f-16142fde533a",
+            "reference": null,
+            "to": "john.doe@example.com",
+            "status": "some-future-status",
+            "created_at": "2021-02-24T14:00:00.000000Z",
+            "completed_at": null,
+            "sent_at": null,
+            "notification_type": "email",
+            "template_id": "217a419e-6a7d-482a-9596-718b889dffce",
+            "template_version": 1
+        }"#;
+
+        let receipt = parse_callback(body).unwrap();
+        assert_eq!(receipt.status, DeliveryStatus::Unknown);
+    }
+
+    #[test]
+    fn verify_bearer_token_accepts_matching_token() {
+        assert!(verify_bearer_token("Bearer my-secret-token", "my-secret-token"));
+    }
+
+    #[test]
+    fn verify_bearer_token_rejects_mismatched_token() {
+        assert!(!verify_bearer_token("Bearer wrong-token", "my-secret-token"));
+        assert!(!verify_bearer_token("my-secret-token", "my-secret-token"));
+    }
+}
@@ -0,0 +1,54 @@
+//! Error types returned by [`crate::NotifyClient`].
+
+use serde::Deserialize;
+use thiserror::Error;
+
+/// A single error entry from Notify's JSON error envelope.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiError {
+    pub error: String,
+    pub message: String,
+}
+
+/// The JSON body Notify returns alongside a non-2xx status code.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct ApiErrorEnvelope {
+    pub status_code: u16,
+    pub errors: Vec<ApiError>,
+}
+
+/// Everything that can go wrong making a request to the Notify API.
+#[derive(Debug, Error)]
+pub enum NotifyError {
+    #[error("failed to create JWT: {0}")]
+    Jwt(#[from] jsonwebtoken::errors::Error),
+
+    #[error("request to Notify failed: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("Notify API returned {status}: {}", format_api_errors(errors))]
+    Api { status: u16, errors: Vec<ApiError> },
+
+    #[error("API key is too short to contain a service id and secret key")]
+    MalformedApiKey,
+
+    #[error("invalid Notify server URL: {0}")]
+    Url(#[from] url::ParseError),
+}
+
+fn format_api_errors(errors: &[ApiError]) -> String {
+    errors
+        .iter()
+        .map(|e| format!("{}: {}", e.error, e.message))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl From<ApiErrorEnvelope> for NotifyError {
+    fn from(envelope: ApiErrorEnvelope) -> Self {
+        NotifyError::Api {
+            status: envelope.status_code,
+            errors: envelope.errors,
+        }
+    }
+}
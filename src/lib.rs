@@ -10,7 +10,7 @@
 //!
 //! async fn mailer() {
 //!     let api_key = String::from("my_test_key-26785a09-ab16-4eb0-8407-a37497a57506-3d844edf-8d35-48ac-975b-e847b4f122b0");
-//!     let notify_client = NotifyClient::new(api_key);
+//!     let notify_client = NotifyClient::new(api_key, None).unwrap();
 //!     let mut personalisation = Map::new();
 //!     let mut personalisation_values = Map::new();
 //!     personalisation_values.insert("my_var".to_string(), Value::String("my value".to_string()));
@@ -23,7 +23,7 @@
 //!
 //! async fn texter() {
 //!     let api_key = String::from("my_test_key-26785a09-ab16-4eb0-8407-a37497a57506-3d844edf-8d35-48ac-975b-e847b4f122b0");
-//!     let notify_client = NotifyClient::new(api_key);
+//!     let notify_client = NotifyClient::new(api_key, None).unwrap();
 //!     let phone_number = String::from("+447900900123");
 //!     let template_id = String::from("217a419e-6a7d-482a-9596-718b889dffce");
 //!
@@ -32,35 +32,98 @@
 //! ```
 
 mod auth;
+pub mod callbacks;
+pub mod error;
+pub mod notification;
+pub mod response;
 
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
 use reqwest;
 use reqwest::header::{AUTHORIZATION, CONTENT_TYPE, USER_AGENT};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
+use url::Url;
+
+use error::ApiErrorEnvelope;
+pub use error::NotifyError;
+pub use notification::{Notification, NotificationList, NotificationQuery};
+pub use response::SendResponse;
 
 static DEFAULT_BASE_URL: &str = "https://api.notifications.service.gov.uk";
 
 pub struct NotifyClient {
-    notify_server: String,
+    notify_server: Url,
     api_key: String,
     client: reqwest::Client,
 }
 
-enum NotificationType {
-    EMAIL,
-    SMS,
+/// The channel a notification is sent over, also used to tag the channel a delivery receipt
+/// was reported for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationType {
+    Email,
+    Sms,
+    Letter,
+}
+
+impl NotificationType {
+    /// The string Notify uses for this channel in JSON bodies and query parameters.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationType::Email => "email",
+            NotificationType::Sms => "sms",
+            NotificationType::Letter => "letter",
+        }
+    }
+}
+
+/// The postage class for a letter, from next-day UK first class down to rest-of-world.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Postage {
+    First,
+    Second,
+    Europe,
+    RestOfWorld,
+}
+
+impl Postage {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Postage::First => "first",
+            Postage::Second => "second",
+            Postage::Europe => "europe",
+            Postage::RestOfWorld => "rest-of-world",
+        }
+    }
 }
 
 impl NotifyClient {
-    pub fn new(api_key: String, notify_server: Option<String>) -> Self {
-        let notify_server = match notify_server {
-            Some(s) => s,
-            None    => DEFAULT_BASE_URL.to_string(),
+    /// Creates a client for `notify_server`, falling back to the production Notify API if
+    /// `None` is given. Returns an error if `notify_server` is not a valid URL.
+    ///
+    /// Any path on `notify_server` (e.g. a reverse proxy mounted under `/notify`) is kept as a
+    /// prefix for every endpoint this client calls.
+    pub fn new(api_key: String, notify_server: Option<String>) -> Result<Self, NotifyError> {
+        let mut notify_server = match notify_server {
+            Some(s) => Url::parse(&s)?,
+            None => Url::parse(DEFAULT_BASE_URL).expect("DEFAULT_BASE_URL is a valid URL"),
         };
-        NotifyClient {
+
+        // Ensure the base path ends in `/` so joining a relative endpoint path appends to it
+        // instead of replacing it.
+        if !notify_server.path().ends_with('/') {
+            notify_server.set_path(&format!("{}/", notify_server.path()));
+        }
+
+        Ok(NotifyClient {
             notify_server,
             api_key,
             client: reqwest::Client::new(),
-        }
+        })
     }
 
     pub async fn send_email(
@@ -69,13 +132,13 @@ impl NotifyClient {
         template_id: String,
         personalisation: Option<Map<String, Value>>,
         reference: Option<String>,
-    ) -> Result<reqwest::Response, reqwest::Error> {
+    ) -> Result<SendResponse, NotifyError> {
         let mut body = Map::new();
         body.insert("email_address".to_string(), Value::String(email_address));
         body.insert("template_id".to_string(), Value::String(template_id));
 
         self.send_notification(
-            NotificationType::EMAIL,
+            NotificationType::Email,
             body,
             personalisation,
             reference,
@@ -91,13 +154,13 @@ impl NotifyClient {
         personalisation: Option<Map<String, Value>>,
         reference: Option<String>,
         sms_sender_id: Option<String>,
-    ) -> Result<reqwest::Response, reqwest::Error> {
+    ) -> Result<SendResponse, NotifyError> {
         let mut body = Map::new();
         body.insert("phone_number".to_string(), Value::String(phone_number));
         body.insert("template_id".to_string(), Value::String(template_id));
 
         self.send_notification(
-            NotificationType::SMS,
+            NotificationType::Sms,
             body,
             personalisation,
             reference,
@@ -106,6 +169,51 @@ impl NotifyClient {
         .await
     }
 
+    /// Sends a templated letter. `personalisation` must supply the recipient's address, using
+    /// the `address_line_1`..`address_line_7`/`postcode` keys the template expects.
+    pub async fn send_letter(
+        &self,
+        template_id: String,
+        personalisation: Map<String, Value>,
+        reference: Option<String>,
+    ) -> Result<SendResponse, NotifyError> {
+        let mut body = Map::new();
+        body.insert("template_id".to_string(), Value::String(template_id));
+
+        self.send_notification(
+            NotificationType::Letter,
+            body,
+            Some(personalisation),
+            reference,
+            None,
+        )
+        .await
+    }
+
+    /// Sends a precompiled letter: a print-ready PDF Notify will post as-is.
+    pub async fn send_precompiled_letter(
+        &self,
+        reference: String,
+        pdf_bytes: &[u8],
+        postage: Option<Postage>,
+    ) -> Result<SendResponse, NotifyError> {
+        let mut body = Map::new();
+        body.insert("reference".to_string(), Value::String(reference));
+        body.insert(
+            "content".to_string(),
+            Value::String(STANDARD.encode(pdf_bytes)),
+        );
+
+        if let Some(postage) = postage {
+            body.insert(
+                "postage".to_string(),
+                Value::String(postage.as_str().to_string()),
+            );
+        }
+
+        self.post("v2/notifications/letter", &body).await
+    }
+
     async fn send_notification(
         &self,
         notification_type: NotificationType,
@@ -113,13 +221,12 @@ impl NotifyClient {
         personalisation: Option<Map<String, Value>>,
         reference: Option<String>,
         sms_sender_id: Option<String>,
-    ) -> Result<reqwest::Response, reqwest::Error> {
+    ) -> Result<SendResponse, NotifyError> {
         let url = match notification_type {
-            NotificationType::EMAIL => "/v2/notifications/email",
-            NotificationType::SMS => "/v2/notifications/sms",
+            NotificationType::Email => "v2/notifications/email",
+            NotificationType::Sms => "v2/notifications/sms",
+            NotificationType::Letter => "v2/notifications/letter",
         };
-        let token = auth::create_jwt(&self.api_key).unwrap();
-        let auth_header: &str = &["Bearer ", token.as_str()].concat();
 
         if let Some(p) = personalisation {
             body.insert("personalisation".to_string(), Value::Object(p));
@@ -133,14 +240,75 @@ impl NotifyClient {
             body.insert("sms_sender_id".to_string(), Value::String(s_id));
         }
 
-        self.client
-            .post(self.notify_server.clone() + url)
+        self.post(url, &body).await
+    }
+
+    async fn post<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        body: &Map<String, Value>,
+    ) -> Result<T, NotifyError> {
+        let response = self
+            .client
+            .post(self.notify_server.join(url)?)
             .header(USER_AGENT, "rust-client-pre-alpha")
-            .header(AUTHORIZATION, auth_header)
+            .header(AUTHORIZATION, self.auth_header()?)
             .header(CONTENT_TYPE, "application/json")
-            .json(&body)
+            .json(body)
             .send()
-            .await
+            .await?;
+
+        Self::parse_response(response).await
+    }
+
+    /// Looks up a single notification by the id Notify assigned it when it was sent.
+    pub async fn get_notification_by_id(&self, id: &str) -> Result<Notification, NotifyError> {
+        let url = format!("v2/notifications/{}", id);
+        let response = self.get(&url).await?;
+        Self::parse_response(response).await
+    }
+
+    /// Lists notifications previously sent by this service, optionally filtered by `query`.
+    pub async fn get_notifications(
+        &self,
+        query: NotificationQuery,
+    ) -> Result<NotificationList, NotifyError> {
+        let response = self
+            .client
+            .get(self.notify_server.join("v2/notifications")?)
+            .header(USER_AGENT, "rust-client-pre-alpha")
+            .header(AUTHORIZATION, self.auth_header()?)
+            .query(&query.query_params())
+            .send()
+            .await?;
+
+        Self::parse_response(response).await
+    }
+
+    async fn get(&self, url: &str) -> Result<reqwest::Response, NotifyError> {
+        Ok(self
+            .client
+            .get(self.notify_server.join(url)?)
+            .header(USER_AGENT, "rust-client-pre-alpha")
+            .header(AUTHORIZATION, self.auth_header()?)
+            .send()
+            .await?)
+    }
+
+    fn auth_header(&self) -> Result<String, NotifyError> {
+        let token = auth::create_jwt(&self.api_key)?;
+        Ok(["Bearer ", token.as_str()].concat())
+    }
+
+    async fn parse_response<T: DeserializeOwned>(
+        response: reqwest::Response,
+    ) -> Result<T, NotifyError> {
+        if !response.status().is_success() {
+            let envelope = response.json::<ApiErrorEnvelope>().await?;
+            return Err(envelope.into());
+        }
+
+        Ok(response.json::<T>().await?)
     }
 }
 
@@ -164,7 +332,7 @@ mod tests {
             .send_email(email_address, template_id, Some(personalisation), None)
             .await
             .unwrap();
-        assert_eq!(response.status(), 201)
+        assert!(!response.id.is_empty())
     }
 
     #[tokio::test]
@@ -176,7 +344,7 @@ mod tests {
             .send_email(email_address, template_id, None, Some(reference))
             .await
             .unwrap();
-        assert_eq!(response.status(), 201)
+        assert!(!response.id.is_empty())
     }
 
     #[tokio::test]
@@ -192,7 +360,7 @@ mod tests {
             .send_sms(phone_number, template_id, Some(personalisation), None, None)
             .await
             .unwrap();
-        assert_eq!(response.status(), 201)
+        assert!(!response.id.is_empty())
     }
 
     #[tokio::test]
@@ -211,7 +379,7 @@ mod tests {
             )
             .await
             .unwrap();
-        assert_eq!(response.status(), 201)
+        assert!(!response.id.is_empty())
     }
 
     #[cfg(test)]
@@ -220,6 +388,6 @@ mod tests {
         let api_key = env::var("GOVUK_NOTIFY_API_KEY")
             .expect("No GOVUK_NOTIFY_API_KEY environment variable found");
 
-        NotifyClient::new(api_key)
+        NotifyClient::new(api_key, None).unwrap()
     }
 }
@@ -0,0 +1,98 @@
+//! Reading back notifications that have already been sent.
+
+use serde::{Deserialize, Serialize};
+
+use crate::callbacks::DeliveryStatus;
+use crate::response::SendResponseTemplate;
+use crate::NotificationType;
+
+/// A notification as returned by `GET /v2/notifications/{id}` or `GET /v2/notifications`.
+///
+/// `template`/`body` are absent for a precompiled letter, which has no template to render.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Notification {
+    pub id: String,
+    pub reference: Option<String>,
+    pub email_address: Option<String>,
+    pub phone_number: Option<String>,
+    pub line_1: Option<String>,
+    #[serde(rename = "type")]
+    pub notification_type: NotificationType,
+    pub status: DeliveryStatus,
+    pub template: Option<SendResponseTemplate>,
+    pub body: Option<String>,
+    pub subject: Option<String>,
+    pub created_at: String,
+    pub sent_at: Option<String>,
+    pub completed_at: Option<String>,
+}
+
+/// The body of `GET /v2/notifications`: a page of notifications plus a link to the next page.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct NotificationList {
+    pub notifications: Vec<Notification>,
+    pub links: NotificationListLinks,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct NotificationListLinks {
+    pub current: String,
+    pub next: Option<String>,
+}
+
+/// Builds up the query parameters accepted by `GET /v2/notifications`.
+#[derive(Debug, Clone, Default)]
+pub struct NotificationQuery {
+    status: Option<DeliveryStatus>,
+    template_type: Option<NotificationType>,
+    reference: Option<String>,
+    older_than: Option<String>,
+}
+
+impl NotificationQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn status(mut self, status: DeliveryStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    pub fn template_type(mut self, template_type: NotificationType) -> Self {
+        self.template_type = Some(template_type);
+        self
+    }
+
+    pub fn reference(mut self, reference: impl Into<String>) -> Self {
+        self.reference = Some(reference.into());
+        self
+    }
+
+    pub fn older_than(mut self, notification_id: impl Into<String>) -> Self {
+        self.older_than = Some(notification_id.into());
+        self
+    }
+
+    pub(crate) fn query_params(&self) -> Vec<(&'static str, String)> {
+        let mut params = Vec::new();
+
+        if let Some(status) = self.status {
+            params.push(("status", status.as_str().to_string()));
+        }
+
+        if let Some(template_type) = self.template_type {
+            params.push(("template_type", template_type.as_str().to_string()));
+        }
+
+        if let Some(reference) = &self.reference {
+            params.push(("reference", reference.clone()));
+        }
+
+        if let Some(older_than) = &self.older_than {
+            params.push(("older_than", older_than.clone()));
+        }
+
+        params
+    }
+}
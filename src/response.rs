@@ -0,0 +1,33 @@
+//! Typed responses returned by the Notify send endpoints.
+
+use serde::{Deserialize, Serialize};
+
+/// The body Notify returns for a successful `POST /v2/notifications/*` call.
+///
+/// `template`/`content` are absent for a precompiled letter, which has no template to render.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SendResponse {
+    pub id: String,
+    pub reference: Option<String>,
+    pub uri: String,
+    pub template: Option<SendResponseTemplate>,
+    pub content: Option<SendResponseContent>,
+}
+
+/// The template that was used to render the notification.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SendResponseTemplate {
+    pub id: String,
+    pub version: u32,
+    pub uri: String,
+}
+
+/// The rendered content of the notification. `subject`/`from_email` are only present for
+/// emails, and `from_number` only for text messages.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SendResponseContent {
+    pub body: String,
+    pub subject: Option<String>,
+    pub from_email: Option<String>,
+    pub from_number: Option<String>,
+}